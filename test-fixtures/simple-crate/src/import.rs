@@ -0,0 +1,183 @@
+use std::fmt;
+use std::io::Read;
+
+use crate::models::User;
+use crate::services::UserService;
+use crate::utils::{format_name, reserve_id_above};
+use crate::RepositoryError;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub rows_read: usize,
+    pub inserted: usize,
+    pub skipped: usize,
+    pub errored: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    Csv(String),
+    Repository(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Csv(msg) => write!(f, "failed to read CSV input: {msg}"),
+            ImportError::Repository(msg) => write!(f, "storage error during import: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<RepositoryError> for ImportError {
+    fn from(err: RepositoryError) -> Self {
+        ImportError::Repository(err.to_string())
+    }
+}
+
+impl UserService {
+    /// Imports users from a `id,name,email` CSV stream in one pass. Malformed
+    /// rows, duplicate ids, and name/email collisions with an already-imported
+    /// row are all counted rather than aborting the import. An I/O error from
+    /// the underlying reader aborts the import with `ImportError::Csv`, and any
+    /// other storage error aborts it with `ImportError::Repository`. Once the
+    /// stream is exhausted, the id counter is
+    /// advanced past the highest imported id so later `generate_id()` calls
+    /// can't collide with it.
+    pub async fn import_csv<R: Read>(&self, reader: R) -> Result<ImportSummary, ImportError> {
+        let mut csv_reader = csv::ReaderBuilder::new().has_headers(false).from_reader(reader);
+        let mut summary = ImportSummary::default();
+        let mut max_id = 0u64;
+
+        for result in csv_reader.records() {
+            summary.rows_read += 1;
+
+            let record = match result {
+                Ok(record) => record,
+                // An I/O error means the underlying reader is broken and further
+                // rows can't be trusted; anything else is just a malformed row.
+                Err(err) if matches!(err.kind(), csv::ErrorKind::Io(_)) => {
+                    return Err(ImportError::Csv(err.to_string()));
+                }
+                Err(_) => {
+                    summary.errored += 1;
+                    continue;
+                }
+            };
+
+            let (id_field, name_field) = match (record.get(0), record.get(1)) {
+                (Some(id), Some(name)) => (id, name),
+                _ => {
+                    summary.errored += 1;
+                    continue;
+                }
+            };
+
+            let id: u64 = match id_field.trim().parse() {
+                Ok(id) => id,
+                Err(_) => {
+                    summary.errored += 1;
+                    continue;
+                }
+            };
+
+            if name_field.trim().is_empty() {
+                summary.errored += 1;
+                continue;
+            }
+
+            if self.store.find(id).await?.is_some() {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let email = record
+                .get(2)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string);
+
+            let user = User {
+                id,
+                name: format_name(name_field),
+                email,
+            };
+
+            match self.store.save(&user).await {
+                Ok(()) => {
+                    max_id = max_id.max(id);
+                    summary.inserted += 1;
+                }
+                // A name/email collision with an already-imported row is a bad
+                // row, not a fatal failure — count it and keep importing.
+                Err(RepositoryError::Duplicate(_)) => summary.errored += 1,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        reserve_id_above(max_id);
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::generate_id;
+
+    #[tokio::test]
+    async fn mixed_csv_produces_the_right_summary_counts() {
+        let service = UserService::new();
+        let csv = "1,Alice,a@x.com\nnotanumber,Bob,b@x.com\n2,,c@x.com\n3,Carol,\n3,Dave,d@x.com\n";
+
+        let summary = service.import_csv(csv.as_bytes()).await.unwrap();
+
+        assert_eq!(
+            summary,
+            ImportSummary {
+                rows_read: 5,
+                inserted: 2,
+                skipped: 1,
+                errored: 2,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn imported_row_without_an_email_has_no_email() {
+        let service = UserService::new();
+        let summary = service.import_csv("1,Carol,".as_bytes()).await.unwrap();
+
+        assert_eq!(summary.inserted, 1);
+        let carol = service.find_by_name("Carol").await.unwrap().unwrap();
+        assert_eq!(carol.email, None);
+    }
+
+    #[tokio::test]
+    async fn a_name_email_collision_with_an_imported_row_is_counted_not_fatal() {
+        let service = UserService::new();
+        let csv = "1,Alice,a@x.com\n2,Alice,b@x.com\n3,Carol,c@x.com\n";
+
+        let summary = service.import_csv(csv.as_bytes()).await.unwrap();
+
+        assert_eq!(
+            summary,
+            ImportSummary {
+                rows_read: 3,
+                inserted: 2,
+                skipped: 0,
+                errored: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn import_advances_the_id_counter_past_the_highest_imported_id() {
+        let service = UserService::new();
+        service.import_csv("5000,Alice,a@x.com\n".as_bytes()).await.unwrap();
+
+        assert!(generate_id() > 5000);
+    }
+}
@@ -1,7 +1,14 @@
+pub mod import;
+pub mod membership;
 pub mod models;
 pub mod services;
+pub mod store;
 pub mod utils;
 
+use std::fmt;
+
+use async_trait::async_trait;
+
 use models::User;
 use services::UserService;
 
@@ -10,7 +17,29 @@ pub fn create_user(name: &str) -> User {
     service.create(name)
 }
 
+#[async_trait]
 pub trait Repository<T> {
-    fn save(&self, item: &T) -> Result<(), String>;
-    fn find(&self, id: u64) -> Option<T>;
+    async fn save(&self, item: &T) -> Result<(), RepositoryError>;
+    async fn find(&self, id: u64) -> Result<Option<T>, RepositoryError>;
+    async fn delete(&self, id: u64) -> Result<(), RepositoryError>;
+    async fn list(&self) -> Result<Vec<T>, RepositoryError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepositoryError {
+    NotFound(u64),
+    Duplicate(u64),
+    Backend(String),
 }
+
+impl fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepositoryError::NotFound(id) => write!(f, "no record with id {id}"),
+            RepositoryError::Duplicate(id) => write!(f, "record {id} conflicts with an existing entry"),
+            RepositoryError::Backend(msg) => write!(f, "storage backend error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {}
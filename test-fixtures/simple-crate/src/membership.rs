@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::models::{Owner, Team, UserRole};
+use crate::utils::generate_id;
+
+/// A membership's holder is an `Owner`, not a bare user id, so that a team
+/// can itself be a member of another team.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Membership {
+    pub member: Owner,
+    pub role: UserRole,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MembershipError {
+    TeamNotFound(u64),
+    NotAMember { team_id: u64, member: Owner },
+    Backend(String),
+}
+
+impl fmt::Display for MembershipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MembershipError::TeamNotFound(id) => write!(f, "no team with id {id}"),
+            MembershipError::NotAMember { team_id, member } => {
+                write!(f, "{member:?} is not a member of team {team_id}")
+            }
+            MembershipError::Backend(msg) => write!(f, "membership backend error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for MembershipError {}
+
+#[derive(Default)]
+struct Inner {
+    teams: HashMap<u64, Team>,
+    memberships: HashMap<u64, Vec<Membership>>,
+}
+
+/// Tracks teams and the role each member (user or team) holds within them.
+/// Memberships are stored as an adjacency list keyed by team id, so role
+/// lookups within a team don't require scanning every team.
+#[derive(Default)]
+pub struct MembershipService {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl MembershipService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Inner>, MembershipError> {
+        self.inner
+            .lock()
+            .map_err(|_| MembershipError::Backend("membership store lock poisoned".to_string()))
+    }
+
+    pub fn create_team(&self, name: &str) -> Result<Team, MembershipError> {
+        let team = Team {
+            id: generate_id(),
+            name: name.to_string(),
+            members: Vec::new(),
+        };
+        let mut inner = self.lock()?;
+        inner.teams.insert(team.id, team.clone());
+        inner.memberships.insert(team.id, Vec::new());
+        Ok(team)
+    }
+
+    pub fn add_member(&self, team_id: u64, member: Owner, role: UserRole) -> Result<(), MembershipError> {
+        let mut inner = self.lock()?;
+        if !inner.teams.contains_key(&team_id) {
+            return Err(MembershipError::TeamNotFound(team_id));
+        }
+        let memberships = inner.memberships.entry(team_id).or_default();
+        if let Some(existing) = memberships.iter_mut().find(|m| m.member == member) {
+            existing.role = role;
+        } else {
+            memberships.push(Membership { member, role });
+        }
+        if let Some(team) = inner.teams.get_mut(&team_id) {
+            if !team.members.contains(&member) {
+                team.members.push(member);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn remove_member(&self, team_id: u64, member: Owner) -> Result<(), MembershipError> {
+        let mut inner = self.lock()?;
+        let memberships = inner
+            .memberships
+            .get_mut(&team_id)
+            .ok_or(MembershipError::TeamNotFound(team_id))?;
+        let before = memberships.len();
+        memberships.retain(|m| m.member != member);
+        if memberships.len() == before {
+            return Err(MembershipError::NotAMember { team_id, member });
+        }
+        if let Some(team) = inner.teams.get_mut(&team_id) {
+            team.members.retain(|&m| m != member);
+        }
+        Ok(())
+    }
+
+    /// Which teams is `member` (a user or a team) a member of.
+    pub fn teams_for(&self, member: Owner) -> Result<Vec<u64>, MembershipError> {
+        let inner = self.lock()?;
+        Ok(inner
+            .memberships
+            .iter()
+            .filter(|(_, members)| members.iter().any(|m| m.member == member))
+            .map(|(&team_id, _)| team_id)
+            .collect())
+    }
+
+    /// What role does `member` hold in `team_id`, if any.
+    pub fn role_of(&self, member: Owner, team_id: u64) -> Result<Option<UserRole>, MembershipError> {
+        let inner = self.lock()?;
+        Ok(inner
+            .memberships
+            .get(&team_id)
+            .and_then(|memberships| memberships.iter().find(|m| m.member == member))
+            .map(|m| m.role))
+    }
+
+    /// Which teams is the user `user_id` a member of.
+    pub fn teams_for_user(&self, user_id: u64) -> Result<Vec<u64>, MembershipError> {
+        self.teams_for(Owner::user(user_id))
+    }
+
+    /// What role does the user `user_id` hold in `team_id`, if any.
+    pub fn role_in_team(&self, user_id: u64, team_id: u64) -> Result<Option<UserRole>, MembershipError> {
+        self.role_of(Owner::user(user_id), team_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_member_then_query_membership_and_role() {
+        let service = MembershipService::new();
+        let team = service.create_team("Engineering").unwrap();
+        let alice = Owner::user(1);
+
+        service.add_member(team.id, alice, UserRole::Member).unwrap();
+
+        assert_eq!(service.teams_for(alice).unwrap(), vec![team.id]);
+        assert_eq!(service.role_of(alice, team.id).unwrap(), Some(UserRole::Member));
+        assert_eq!(service.teams_for_user(1).unwrap(), vec![team.id]);
+        assert_eq!(service.role_in_team(1, team.id).unwrap(), Some(UserRole::Member));
+    }
+
+    #[test]
+    fn re_adding_a_member_overwrites_their_role() {
+        let service = MembershipService::new();
+        let team = service.create_team("Engineering").unwrap();
+        let alice = Owner::user(1);
+
+        service.add_member(team.id, alice, UserRole::Member).unwrap();
+        service.add_member(team.id, alice, UserRole::Admin).unwrap();
+
+        assert_eq!(service.role_of(alice, team.id).unwrap(), Some(UserRole::Admin));
+    }
+
+    #[test]
+    fn remove_member_drops_membership_and_allows_re_adding() {
+        let service = MembershipService::new();
+        let team = service.create_team("Engineering").unwrap();
+        let alice = Owner::user(1);
+
+        service.add_member(team.id, alice, UserRole::Member).unwrap();
+        service.remove_member(team.id, alice).unwrap();
+
+        assert_eq!(service.role_of(alice, team.id).unwrap(), None);
+        assert!(service.teams_for(alice).unwrap().is_empty());
+
+        service.add_member(team.id, alice, UserRole::Guest).unwrap();
+        assert_eq!(service.role_of(alice, team.id).unwrap(), Some(UserRole::Guest));
+    }
+
+    #[test]
+    fn a_team_can_be_a_member_of_another_team() {
+        let service = MembershipService::new();
+        let parent = service.create_team("Org").unwrap();
+        let child = service.create_team("Sub-team").unwrap();
+
+        service
+            .add_member(parent.id, Owner::team(child.id), UserRole::Member)
+            .unwrap();
+
+        assert_eq!(
+            service.role_of(Owner::team(child.id), parent.id).unwrap(),
+            Some(UserRole::Member)
+        );
+    }
+
+    #[test]
+    fn queries_for_a_non_member_return_nothing() {
+        let service = MembershipService::new();
+        let team = service.create_team("Engineering").unwrap();
+
+        assert_eq!(service.role_in_team(42, team.id).unwrap(), None);
+        assert!(service.teams_for_user(42).unwrap().is_empty());
+    }
+
+    #[test]
+    fn add_member_on_unknown_team_is_an_error() {
+        let service = MembershipService::new();
+        let err = service.add_member(404, Owner::user(1), UserRole::Member).unwrap_err();
+        assert_eq!(err, MembershipError::TeamNotFound(404));
+    }
+
+    #[test]
+    fn remove_member_on_unknown_team_is_an_error() {
+        let service = MembershipService::new();
+        let err = service.remove_member(404, Owner::user(1)).unwrap_err();
+        assert_eq!(err, MembershipError::TeamNotFound(404));
+    }
+
+    #[test]
+    fn remove_member_who_was_never_added_is_an_error() {
+        let service = MembershipService::new();
+        let team = service.create_team("Engineering").unwrap();
+        let err = service.remove_member(team.id, Owner::user(1)).unwrap_err();
+        assert_eq!(
+            err,
+            MembershipError::NotAMember {
+                team_id: team.id,
+                member: Owner::user(1)
+            }
+        );
+    }
+}
@@ -22,13 +22,46 @@ impl User {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UserRole {
     Admin,
     Member,
     Guest,
 }
 
+/// Distinguishes an individual user from a team as the holder of something,
+/// e.g. a resource or a membership.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnerKind {
+    User,
+    Team,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Owner {
+    pub kind: OwnerKind,
+    pub id: u64,
+}
+
+impl Owner {
+    pub fn user(id: u64) -> Self {
+        Self { kind: OwnerKind::User, id }
+    }
+
+    pub fn team(id: u64) -> Self {
+        Self { kind: OwnerKind::Team, id }
+    }
+}
+
+/// A member list holds `Owner`s rather than bare user ids so that a team can
+/// itself be a member of another team, not just individual users.
+#[derive(Debug, Clone)]
+pub struct Team {
+    pub id: u64,
+    pub name: String,
+    pub members: Vec<Owner>,
+}
+
 pub trait Identifiable {
     fn id(&self) -> u64;
 }
@@ -1,21 +1,56 @@
-use crate::models::{User, Identifiable};
-use crate::Repository;
+use async_trait::async_trait;
+
+use crate::models::User;
+use crate::store::{InMemoryUserStore, UserStore};
+use crate::utils::trigram_similarity;
+use crate::{Repository, RepositoryError};
 
 pub struct UserService {
-    users: Vec<User>,
+    pub(crate) store: Box<dyn UserStore>,
 }
 
 impl UserService {
     pub fn new() -> Self {
-        Self { users: Vec::new() }
+        Self::with_store(Box::new(InMemoryUserStore::new()))
+    }
+
+    pub fn with_store(store: Box<dyn UserStore>) -> Self {
+        Self { store }
     }
 
     pub fn create(&self, name: &str) -> User {
         User::new(name)
     }
 
-    pub fn find_by_name(&self, name: &str) -> Option<&User> {
-        self.users.iter().find(|u| u.name == name)
+    pub async fn find_by_name(&self, name: &str) -> Result<Option<User>, RepositoryError> {
+        self.store.find_by_name(name).await
+    }
+
+    pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, RepositoryError> {
+        self.store.find_by_email(email).await
+    }
+
+    /// Typo-tolerant lookup: ranks every user by the best trigram similarity
+    /// of `query` against their name and email, dropping zero-score matches
+    /// and returning at most `limit` results, best first.
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<(User, f32)>, RepositoryError> {
+        let users = self.store.list().await?;
+        let mut scored: Vec<(User, f32)> = users
+            .into_iter()
+            .filter_map(|user| {
+                let name_score = trigram_similarity(query, &user.name);
+                let email_score = user
+                    .email
+                    .as_deref()
+                    .map(|email| trigram_similarity(query, email))
+                    .unwrap_or(0.0);
+                let score = name_score.max(email_score);
+                (score > 0.0).then_some((user, score))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+        Ok(scored)
     }
 }
 
@@ -25,12 +60,38 @@ impl Default for UserService {
     }
 }
 
+#[async_trait]
 impl Repository<User> for UserService {
-    fn save(&self, _item: &User) -> Result<(), String> {
-        Ok(())
+    async fn save(&self, item: &User) -> Result<(), RepositoryError> {
+        self.store.save(item).await
+    }
+
+    async fn find(&self, id: u64) -> Result<Option<User>, RepositoryError> {
+        self.store.find(id).await
     }
 
-    fn find(&self, id: u64) -> Option<User> {
-        self.users.iter().find(|u| u.id() == id).cloned()
+    async fn delete(&self, id: u64) -> Result<(), RepositoryError> {
+        self.store.delete(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<User>, RepositoryError> {
+        self.store.list().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn save_find_delete_round_trip_through_the_repository_trait() {
+        let service = UserService::new();
+        let user = service.create("Alice");
+
+        service.save(&user).await.unwrap();
+        assert_eq!(service.find(user.id).await.unwrap().unwrap().id, user.id);
+
+        service.delete(user.id).await.unwrap();
+        assert!(service.find(user.id).await.unwrap().is_none());
     }
 }
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::models::User;
+use crate::utils::format_name;
+use crate::RepositoryError;
+
+#[async_trait]
+pub trait UserStore: Send + Sync {
+    async fn save(&self, user: &User) -> Result<(), RepositoryError>;
+    async fn find(&self, id: u64) -> Result<Option<User>, RepositoryError>;
+    async fn delete(&self, id: u64) -> Result<(), RepositoryError>;
+    async fn list(&self) -> Result<Vec<User>, RepositoryError>;
+    async fn find_by_name(&self, name: &str) -> Result<Option<User>, RepositoryError>;
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, RepositoryError>;
+}
+
+#[derive(Default)]
+struct Inner {
+    users: HashMap<u64, User>,
+    by_name: HashMap<String, u64>,
+    by_email: HashMap<String, u64>,
+}
+
+impl Inner {
+    fn check_unique(&self, user: &User) -> Result<(), RepositoryError> {
+        let name_key = format_name(&user.name);
+        if let Some(&existing) = self.by_name.get(&name_key) {
+            if existing != user.id {
+                return Err(RepositoryError::Duplicate(user.id));
+            }
+        }
+        if let Some(email) = &user.email {
+            let email_key = format_name(email);
+            if let Some(&existing) = self.by_email.get(&email_key) {
+                if existing != user.id {
+                    return Err(RepositoryError::Duplicate(user.id));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn reindex(&mut self, user: &User) {
+        if let Some(previous) = self.users.get(&user.id) {
+            self.by_name.remove(&format_name(&previous.name));
+            if let Some(email) = &previous.email {
+                self.by_email.remove(&format_name(email));
+            }
+        }
+        self.by_name.insert(format_name(&user.name), user.id);
+        if let Some(email) = &user.email {
+            self.by_email.insert(format_name(email), user.id);
+        }
+    }
+
+    fn unindex(&mut self, user: &User) {
+        self.by_name.remove(&format_name(&user.name));
+        if let Some(email) = &user.email {
+            self.by_email.remove(&format_name(email));
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryUserStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl InMemoryUserStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Inner>, RepositoryError> {
+        self.inner
+            .lock()
+            .map_err(|_| RepositoryError::Backend("in-memory store lock poisoned".to_string()))
+    }
+}
+
+#[async_trait]
+impl UserStore for InMemoryUserStore {
+    async fn save(&self, user: &User) -> Result<(), RepositoryError> {
+        let mut inner = self.lock()?;
+        inner.check_unique(user)?;
+        inner.reindex(user);
+        inner.users.insert(user.id, user.clone());
+        Ok(())
+    }
+
+    async fn find(&self, id: u64) -> Result<Option<User>, RepositoryError> {
+        let inner = self.lock()?;
+        Ok(inner.users.get(&id).cloned())
+    }
+
+    async fn delete(&self, id: u64) -> Result<(), RepositoryError> {
+        let mut inner = self.lock()?;
+        if let Some(user) = inner.users.remove(&id) {
+            inner.unindex(&user);
+        }
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<User>, RepositoryError> {
+        let inner = self.lock()?;
+        Ok(inner.users.values().cloned().collect())
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<User>, RepositoryError> {
+        let inner = self.lock()?;
+        let id = inner.by_name.get(&format_name(name));
+        Ok(id.and_then(|id| inner.users.get(id)).cloned())
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, RepositoryError> {
+        let inner = self.lock()?;
+        let id = inner.by_email.get(&format_name(email));
+        Ok(id.and_then(|id| inner.users.get(id)).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: u64, name: &str, email: Option<&str>) -> User {
+        User {
+            id,
+            name: name.to_string(),
+            email: email.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn find_by_name_and_email_are_case_and_whitespace_insensitive() {
+        let store = InMemoryUserStore::new();
+        store.save(&user(1, "Alice", Some("Alice@Example.com"))).await.unwrap();
+
+        assert_eq!(store.find_by_name("  alice ").await.unwrap().unwrap().id, 1);
+        assert_eq!(store.find_by_email("alice@example.com").await.unwrap().unwrap().id, 1);
+    }
+
+    #[tokio::test]
+    async fn save_rejects_duplicate_name_for_a_different_id() {
+        let store = InMemoryUserStore::new();
+        store.save(&user(1, "Alice", None)).await.unwrap();
+
+        let err = store.save(&user(2, "Alice", None)).await.unwrap_err();
+        assert_eq!(err, RepositoryError::Duplicate(2));
+    }
+
+    #[tokio::test]
+    async fn save_rejects_duplicate_email_for_a_different_id() {
+        let store = InMemoryUserStore::new();
+        store.save(&user(1, "Alice", Some("alice@example.com"))).await.unwrap();
+
+        let err = store
+            .save(&user(2, "Bob", Some("alice@example.com")))
+            .await
+            .unwrap_err();
+        assert_eq!(err, RepositoryError::Duplicate(2));
+    }
+
+    #[tokio::test]
+    async fn resaving_with_a_cleared_email_drops_the_stale_index_entry() {
+        let store = InMemoryUserStore::new();
+        store.save(&user(1, "Alice", Some("alice@example.com"))).await.unwrap();
+        store.save(&user(1, "Alice", None)).await.unwrap();
+
+        assert!(store.find_by_email("alice@example.com").await.unwrap().is_none());
+        // The old email is now free for a different user to claim.
+        store.save(&user(2, "Bob", Some("alice@example.com"))).await.unwrap();
+        assert_eq!(
+            store.find_by_email("alice@example.com").await.unwrap().unwrap().id,
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_removes_name_and_email_index_entries() {
+        let store = InMemoryUserStore::new();
+        store.save(&user(1, "Alice", Some("alice@example.com"))).await.unwrap();
+        store.delete(1).await.unwrap();
+
+        assert!(store.find_by_name("Alice").await.unwrap().is_none());
+        assert!(store.find_by_email("alice@example.com").await.unwrap().is_none());
+        assert!(store.list().await.unwrap().is_empty());
+    }
+}
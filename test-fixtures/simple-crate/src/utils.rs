@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 static COUNTER: AtomicU64 = AtomicU64::new(1);
@@ -6,10 +7,50 @@ pub fn generate_id() -> u64 {
     COUNTER.fetch_add(1, Ordering::SeqCst)
 }
 
+/// Advances the id counter so that it is strictly greater than `n`, without
+/// ever moving it backwards. Used after a bulk import to make sure later
+/// `generate_id()` calls can't collide with imported ids.
+pub fn reserve_id_above(n: u64) -> u64 {
+    let mut current = COUNTER.load(Ordering::SeqCst);
+    loop {
+        if current > n {
+            return current;
+        }
+        match COUNTER.compare_exchange(current, n + 1, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(_) => return n + 1,
+            Err(actual) => current = actual,
+        }
+    }
+}
+
 pub fn format_name(name: &str) -> String {
     name.trim().to_lowercase()
 }
 
+/// Collects the set of 3-character sliding windows over `s`, after padding
+/// with two leading spaces and one trailing space. Strings shorter than
+/// three characters (before padding) are treated as a single gram.
+pub fn trigrams(s: &str) -> HashSet<String> {
+    if s.chars().count() < 3 {
+        return HashSet::from([format!("  {s} ")]);
+    }
+    let padded = format!("  {s} ");
+    let chars: Vec<char> = padded.chars().collect();
+    chars.windows(3).map(|w| w.iter().collect()).collect()
+}
+
+/// Jaccard similarity (`|intersection| / |union|`) between the trigram sets
+/// of `a` and `b`, after normalizing both with [`format_name`].
+pub fn trigram_similarity(a: &str, b: &str) -> f32 {
+    let a_grams = trigrams(&format_name(a));
+    let b_grams = trigrams(&format_name(b));
+    let union = a_grams.union(&b_grams).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a_grams.intersection(&b_grams).count() as f32 / union as f32
+}
+
 pub struct Config {
     pub max_users: usize,
     pub timeout_ms: u64,
@@ -23,3 +64,43 @@ impl Default for Config {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trigrams_of_short_string_is_a_single_gram() {
+        assert_eq!(trigrams("a"), HashSet::from(["  a ".to_string()]));
+        assert_eq!(trigrams("ab"), HashSet::from(["  ab ".to_string()]));
+    }
+
+    #[test]
+    fn trigrams_of_longer_string_slides_a_window() {
+        let grams = trigrams("cat");
+        assert_eq!(
+            grams,
+            HashSet::from([
+                "  c".to_string(),
+                " ca".to_string(),
+                "cat".to_string(),
+                "at ".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn similarity_of_identical_strings_is_one() {
+        assert_eq!(trigram_similarity("alice", "alice"), 1.0);
+    }
+
+    #[test]
+    fn similarity_of_unrelated_strings_is_zero() {
+        assert_eq!(trigram_similarity("alice", "zzz"), 0.0);
+    }
+
+    #[test]
+    fn similarity_is_case_and_whitespace_insensitive() {
+        assert_eq!(trigram_similarity("Alice", "  alice  "), 1.0);
+    }
+}